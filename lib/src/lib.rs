@@ -1,11 +1,14 @@
+use std::collections::{HashSet, VecDeque};
 use std::fmt::{Debug, Display, Error, Formatter};
-use std::ops::Add;
+use std::iter::FusedIterator;
+use std::ops::{Add, Range};
 
 #[derive(Debug, Clone)]
 pub struct Grid<T: Debug> {
     grid: Vec<T>,
     grid_size: (usize, usize),
-    offset: usize,
+    lower_bounds: (isize, isize),
+    upper_bounds: (isize, isize),
 }
 
 impl<T: Display + Debug> Display for Grid<T> {
@@ -33,30 +36,130 @@ impl<T: Default + Debug> Grid<T> {
             data.insert(i, T::default())
         }
 
+        let lower_bounds = (-(x_offset as isize), -(y_offset as isize));
+        let upper_bounds = (lower_bounds.0 + width as isize, lower_bounds.1 + height as isize);
+
         Grid {
             grid: data,
             grid_size: (width, height),
-            offset: y_offset * height + x_offset,
+            lower_bounds,
+            upper_bounds,
         }
     }
 }
 
+impl Grid<char> {
+    pub fn from_str_map(input: &str) -> Result<Grid<char>, String> {
+        Grid::from_chars_with(input, |c| c)
+    }
+}
+
 impl<T: Debug> Grid<T> {
     fn coords_to_index(&self, x: isize, y: isize) -> usize {
-        ((y * self.grid_size.0 as isize + x) + self.offset as isize) as usize
+        let width = self.grid_size.0 as isize;
+        let (lower_x, lower_y) = self.lower_bounds;
+        ((y - lower_y) * width + (x - lower_x)) as usize
+    }
+
+    pub fn with_generator(width: usize, height: usize, f: impl Fn(isize, isize) -> T) -> Grid<T> {
+        let mut grid = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                grid.push(f(x as isize, y as isize));
+            }
+        }
+
+        Grid {
+            grid,
+            grid_size: (width, height),
+            lower_bounds: (0, 0),
+            upper_bounds: (width as isize, height as isize),
+        }
+    }
+
+    pub fn from_chars_with(input: &str, f: impl Fn(char) -> T) -> Result<Grid<T>, String> {
+        let lines: Vec<&str> = input.lines().collect();
+        let height = lines.len();
+        if height == 0 {
+            return Err("cannot build a grid from an empty string".to_string());
+        }
+
+        let width = lines[0].chars().count();
+        if lines.iter().any(|line| line.chars().count() != width) {
+            return Err("cannot build a grid from ragged lines".to_string());
+        }
+
+        let grid = lines
+            .iter()
+            .flat_map(|line| line.chars().map(&f))
+            .collect();
+
+        Ok(Grid {
+            grid,
+            grid_size: (width, height),
+            lower_bounds: (0, 0),
+            upper_bounds: (width as isize, height as isize),
+        })
     }
 
     pub fn get_row(&self, row_idx: usize) -> &[T] {
-        let (width, height) = self.grid_size;
+        let (width, _) = self.grid_size;
         let offset = row_idx * width;
         &self.grid[offset..offset + width]
     }
+
+    pub fn get_col(&self, col_idx: usize) -> Vec<&T> {
+        let (width, height) = self.grid_size;
+        (0..height).map(|row| &self.grid[row * width + col_idx]).collect()
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        let (width, height) = self.grid_size;
+        (0..height).map(move |row| &self.grid[row * width..row * width + width])
+    }
+
+    pub fn column_iter(&self, col_idx: usize) -> ColumnIter<'_, T> {
+        ColumnIter {
+            grid: self,
+            col: col_idx,
+            row: 0,
+        }
+    }
+
+    pub fn iter_cols(&self) -> impl Iterator<Item = ColumnIter<'_, T>> {
+        let (width, _) = self.grid_size;
+        (0..width).map(move |col| self.column_iter(col))
+    }
+}
+
+pub struct ColumnIter<'a, T: Debug> {
+    grid: &'a Grid<T>,
+    col: usize,
+    row: usize,
+}
+
+impl<'a, T: Debug> Iterator for ColumnIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (width, height) = self.grid.grid_size;
+        if self.row >= height {
+            return None;
+        }
+
+        let item = &self.grid.grid[self.row * width + self.col];
+        self.row += 1;
+        Some(item)
+    }
 }
 
 pub struct GridIntoIterator<T: Debug> {
     grid: Grid<T>,
     x: isize,
     y: isize,
+    back_x: isize,
+    back_y: isize,
 }
 
 #[derive(Debug)]
@@ -71,10 +174,30 @@ impl<T: Debug + Clone> IntoIterator for Grid<T> {
     type IntoIter = GridIntoIterator<T>;
 
     fn into_iter(self) -> Self::IntoIter {
+        let (lower_x, lower_y) = self.lower_bounds;
+        let (upper_x, upper_y) = self.upper_bounds;
         GridIntoIterator {
             grid: self,
-            x: 0,
-            y: 0,
+            x: lower_x,
+            y: lower_y,
+            back_x: upper_x - 1,
+            back_y: upper_y - 1,
+        }
+    }
+}
+
+impl<T: Debug> GridIntoIterator<T> {
+    // Number of cells between the front and back cursors, inclusive of both.
+    fn remaining(&self) -> usize {
+        let width = self.grid.grid_size.0 as isize;
+        let (lower_x, lower_y) = self.grid.lower_bounds;
+        let front = (self.y - lower_y) * width + (self.x - lower_x);
+        let back = (self.back_y - lower_y) * width + (self.back_x - lower_x);
+
+        if front > back {
+            0
+        } else {
+            (back - front + 1) as usize
         }
     }
 }
@@ -82,29 +205,65 @@ impl<T: Debug + Clone> IntoIterator for Grid<T> {
 impl<T: Debug + Clone> Iterator for GridIntoIterator<T> {
     type Item = GridIteratorItem<T>;
     fn next(&mut self) -> Option<GridIteratorItem<T>> {
-        if self.x == self.grid.grid_size.0 as isize {
-            self.x = 0;
+        if self.remaining() == 0 {
+            return None;
+        }
+
+        if self.x == self.grid.upper_bounds.0 {
+            self.x = self.grid.lower_bounds.0;
             self.y += 1;
-            if self.y == self.grid.grid_size.1 as isize {
-                return None;
-            }
         }
 
         let index = self.grid.coords_to_index(self.x, self.y);
-        let cell = self.grid.grid.get(index).map_or(None, |x| {
-            Some(GridIteratorItem {
-                x: self.x,
-                y: self.y,
-                element: x.clone(),
-            })
+        let cell = self.grid.grid.get(index).map(|x| GridIteratorItem {
+            x: self.x,
+            y: self.y,
+            element: x.clone(),
         });
 
         self.x += 1;
 
         cell
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
 }
 
+impl<T: Debug + Clone> ExactSizeIterator for GridIntoIterator<T> {
+    fn len(&self) -> usize {
+        self.remaining()
+    }
+}
+
+impl<T: Debug + Clone> DoubleEndedIterator for GridIntoIterator<T> {
+    fn next_back(&mut self) -> Option<GridIteratorItem<T>> {
+        if self.remaining() == 0 {
+            return None;
+        }
+
+        if self.back_x < self.grid.lower_bounds.0 {
+            self.back_x = self.grid.upper_bounds.0 - 1;
+            self.back_y -= 1;
+        }
+
+        let index = self.grid.coords_to_index(self.back_x, self.back_y);
+        let cell = self.grid.grid.get(index).map(|x| GridIteratorItem {
+            x: self.back_x,
+            y: self.back_y,
+            element: x.clone(),
+        });
+
+        self.back_x -= 1;
+
+        cell
+    }
+}
+
+impl<T: Debug + Clone> FusedIterator for GridIntoIterator<T> {}
+
 impl<T: Debug + Clone> Grid<T> {
     pub fn get(&self, x: isize, y: isize) -> Option<&T> {
         if !self.check_bounds(x, y) {
@@ -115,12 +274,25 @@ impl<T: Debug + Clone> Grid<T> {
     }
 
     fn check_bounds(&self, x: isize, y: isize) -> bool {
-        let (width, height) = self.grid_size;
-        if self.offset == 0 {
-            x < width as isize && y < height as isize && x >= 0 && y >= 0
-        } else {
-            true
-        }
+        let (lower_x, lower_y) = self.lower_bounds;
+        let (upper_x, upper_y) = self.upper_bounds;
+        x >= lower_x && x < upper_x && y >= lower_y && y < upper_y
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        self.check_bounds(x, y)
+    }
+
+    pub fn x_range(&self) -> Range<isize> {
+        self.lower_bounds.0..self.upper_bounds.0
+    }
+
+    pub fn y_range(&self) -> Range<isize> {
+        self.lower_bounds.1..self.upper_bounds.1
+    }
+
+    pub fn bounds(&self) -> ((isize, isize), (isize, isize)) {
+        (self.lower_bounds, self.upper_bounds)
     }
 
     pub fn set(&mut self, x: isize, y: isize, item: T) -> Result<(), ()> {
@@ -138,16 +310,186 @@ impl<T: Debug + Clone> Grid<T> {
     }
 
     pub fn iter(&self) -> GridIntoIterator<T> {
+        let (lower_x, lower_y) = self.lower_bounds;
+        let (upper_x, upper_y) = self.upper_bounds;
         GridIntoIterator {
             grid: self.clone(),
-            x: 0,
-            y: 0,
+            x: lower_x,
+            y: lower_y,
+            back_x: upper_x - 1,
+            back_y: upper_y - 1,
         }
     }
 
     pub fn grid(&self) -> Vec<T> {
         self.grid.clone()
     }
+
+    pub fn neighbors(&self, x: isize, y: isize, diagonal: bool) -> Vec<GridIteratorItem<&T>> {
+        let mut offsets = vec![(0, -1), (0, 1), (-1, 0), (1, 0)];
+        if diagonal {
+            offsets.extend_from_slice(&[(-1, -1), (1, -1), (-1, 1), (1, 1)]);
+        }
+
+        offsets
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let (nx, ny) = (x + dx, y + dy);
+                self.get(nx, ny).map(|element| GridIteratorItem {
+                    element,
+                    x: nx,
+                    y: ny,
+                })
+            })
+            .collect()
+    }
+
+    pub fn flood_fill(
+        &self,
+        start: (isize, isize),
+        predicate: impl Fn(&T) -> bool,
+    ) -> Vec<(isize, isize)> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start);
+        queue.push_back(start);
+
+        while let Some((x, y)) = queue.pop_front() {
+            for neighbor in self.neighbors(x, y, false) {
+                let coord = (neighbor.x, neighbor.y);
+                if !visited.contains(&coord) && predicate(neighbor.element) {
+                    visited.insert(coord);
+                    queue.push_back(coord);
+                }
+            }
+        }
+
+        visited.into_iter().collect()
+    }
+
+    pub fn map<U: Debug + Clone>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid::<U>::from_grid(self, f)
+    }
+
+    pub fn from_grid<S: Debug>(other: &Grid<S>, f: impl Fn(&S) -> T) -> Grid<T> {
+        Grid {
+            grid: other.grid.iter().map(&f).collect(),
+            grid_size: other.grid_size,
+            lower_bounds: other.lower_bounds,
+            upper_bounds: other.upper_bounds,
+        }
+    }
+}
+
+impl<T: Default + Debug + Clone> Grid<T> {
+    pub fn subgrid(&self, x0: isize, y0: isize, width: usize, height: usize) -> Grid<T> {
+        let mut result = Grid::new(width, height, 0, 0);
+
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                if let Some(item) = self.get(x0 + x, y0 + y) {
+                    result.set(x, y, item.clone()).unwrap();
+                }
+            }
+        }
+
+        result
+    }
+
+    fn region_in_bounds(&self, region: (usize, usize, usize, usize)) -> bool {
+        let (x0, y0, x1, y1) = region;
+        let (width, height) = self.grid_size;
+        x0 <= x1 && y0 <= y1 && x1 <= width && y1 <= height
+    }
+
+    pub fn scroll_up(&mut self, region: (usize, usize, usize, usize), n: usize) -> Result<(), ()> {
+        if !self.region_in_bounds(region) {
+            return Err(());
+        }
+
+        let (x0, y0, x1, y1) = region;
+        let width = self.grid_size.0;
+
+        for y in y0..y1 {
+            let src_y = y + n;
+            for x in x0..x1 {
+                let value = if src_y < y1 {
+                    self.grid[src_y * width + x].clone()
+                } else {
+                    T::default()
+                };
+                self.grid[y * width + x] = value;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn scroll_down(&mut self, region: (usize, usize, usize, usize), n: usize) -> Result<(), ()> {
+        if !self.region_in_bounds(region) {
+            return Err(());
+        }
+
+        let (x0, y0, x1, y1) = region;
+        let width = self.grid_size.0;
+
+        for y in (y0..y1).rev() {
+            let value_row = y.checked_sub(n).filter(|&src_y| src_y >= y0);
+            for x in x0..x1 {
+                let value = match value_row {
+                    Some(src_y) => self.grid[src_y * width + x].clone(),
+                    None => T::default(),
+                };
+                self.grid[y * width + x] = value;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn scroll_left(&mut self, region: (usize, usize, usize, usize), n: usize) -> Result<(), ()> {
+        if !self.region_in_bounds(region) {
+            return Err(());
+        }
+
+        let (x0, y0, x1, y1) = region;
+        let width = self.grid_size.0;
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let src_x = x + n;
+                let value = if src_x < x1 {
+                    self.grid[y * width + src_x].clone()
+                } else {
+                    T::default()
+                };
+                self.grid[y * width + x] = value;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn scroll_right(&mut self, region: (usize, usize, usize, usize), n: usize) -> Result<(), ()> {
+        if !self.region_in_bounds(region) {
+            return Err(());
+        }
+
+        let (x0, y0, x1, y1) = region;
+        let width = self.grid_size.0;
+
+        for y in y0..y1 {
+            for x in (x0..x1).rev() {
+                let value = match x.checked_sub(n).filter(|&src_x| src_x >= x0) {
+                    Some(src_x) => self.grid[y * width + src_x].clone(),
+                    None => T::default(),
+                };
+                self.grid[y * width + x] = value;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -165,6 +507,29 @@ mod tests {
         assert_eq!(&true, grid.get(2, 2).unwrap());
     }
 
+    #[test]
+    fn test_row_col_accessors() {
+        let mut grid: Grid<u8> = Grid::new(3, 2, 0, 0);
+        for i in 0..6isize {
+            grid.set(i % 3, i / 3, i as u8).unwrap();
+        }
+
+        assert_eq!(grid.get_row(1), &[3, 4, 5]);
+        assert_eq!(grid.get_col(1), vec![&1, &4]);
+
+        assert_eq!(
+            grid.iter_rows().collect::<Vec<_>>(),
+            vec![&[0, 1, 2][..], &[3, 4, 5][..]]
+        );
+        assert_eq!(grid.column_iter(2).collect::<Vec<_>>(), vec![&2, &5]);
+        assert_eq!(
+            grid.iter_cols()
+                .map(|col| col.collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![&0, &3], vec![&1, &4], vec![&2, &5]]
+        );
+    }
+
     #[test]
     fn test_offset_grid() {
         let mut grid: Grid<bool> = Grid::new(5, 5, 2, 2);
@@ -175,10 +540,181 @@ mod tests {
         assert_eq!(&true, grid.get(-2, -2).unwrap());
     }
 
+    #[test]
+    fn test_offset_grid_rejects_out_of_bounds() {
+        let grid: Grid<bool> = Grid::new(5, 5, 2, 2);
+        assert_eq!(grid.bounds(), ((-2, -2), (3, 3)));
+        assert!(grid.contains(2, 2));
+        assert!(!grid.contains(3, 3));
+        assert!(!grid.contains(-3, -3));
+        assert_eq!(grid.get(3, 3), None);
+    }
+
+    #[test]
+    fn test_from_str_map() {
+        let grid = Grid::from_str_map("#.\n.#").unwrap();
+        assert_eq!(grid.bounds(), ((0, 0), (2, 2)));
+        assert_eq!(*grid.get(0, 0).unwrap(), '#');
+        assert_eq!(*grid.get(1, 0).unwrap(), '.');
+        assert_eq!(*grid.get(0, 1).unwrap(), '.');
+        assert_eq!(*grid.get(1, 1).unwrap(), '#');
+    }
+
+    #[test]
+    fn test_from_str_map_rejects_ragged_lines() {
+        assert!(Grid::from_str_map("##\n#").is_err());
+    }
+
+    #[test]
+    fn test_from_str_map_rejects_empty_input() {
+        assert!(Grid::from_str_map("").is_err());
+    }
+
+    #[test]
+    fn test_from_chars_with() {
+        let grid: Grid<bool> = Grid::from_chars_with("#.\n.#", |c| c == '#').unwrap();
+        assert!(*grid.get(0, 0).unwrap());
+        assert!(!*grid.get(1, 0).unwrap());
+    }
+
+    #[test]
+    fn test_with_generator() {
+        let grid: Grid<isize> = Grid::with_generator(3, 2, |x, y| x + y * 10);
+        assert_eq!(*grid.get(0, 0).unwrap(), 0);
+        assert_eq!(*grid.get(2, 1).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_subgrid_and_map() {
+        let mut grid: Grid<u8> = Grid::new(4, 4, 0, 0);
+        for i in 0..16isize {
+            grid.set(i % 4, i / 4, i as u8).unwrap();
+        }
+
+        let sub = grid.subgrid(1, 1, 2, 2);
+        assert_eq!(*sub.get(0, 0).unwrap(), 5);
+        assert_eq!(*sub.get(1, 1).unwrap(), 10);
+
+        let mapped = sub.map(|value| *value as u32 * 2);
+        assert_eq!(*mapped.get(0, 0).unwrap(), 10);
+        assert_eq!(*mapped.get(1, 1).unwrap(), 20);
+    }
+
+    #[test]
+    fn test_scroll_up() {
+        let mut grid: Grid<i32> = Grid::new(3, 3, 0, 0);
+        for i in 0..9isize {
+            grid.set(i % 3, i / 3, i as i32).unwrap();
+        }
+
+        grid.scroll_up((0, 0, 3, 3), 1).unwrap();
+
+        assert_eq!(grid.get_row(0), &[3, 4, 5]);
+        assert_eq!(grid.get_row(1), &[6, 7, 8]);
+        assert_eq!(grid.get_row(2), &[0, 0, 0]);
+    }
+
+    #[test]
+    fn test_scroll_down() {
+        let mut grid: Grid<i32> = Grid::new(3, 3, 0, 0);
+        for i in 0..9isize {
+            grid.set(i % 3, i / 3, i as i32).unwrap();
+        }
+
+        grid.scroll_down((0, 0, 3, 3), 1).unwrap();
+
+        assert_eq!(grid.get_row(0), &[0, 0, 0]);
+        assert_eq!(grid.get_row(1), &[0, 1, 2]);
+        assert_eq!(grid.get_row(2), &[3, 4, 5]);
+    }
+
+    #[test]
+    fn test_scroll_left() {
+        let mut grid: Grid<i32> = Grid::new(3, 1, 0, 0);
+        grid.set(0, 0, 1).unwrap();
+        grid.set(1, 0, 2).unwrap();
+        grid.set(2, 0, 3).unwrap();
+
+        grid.scroll_left((0, 0, 3, 1), 1).unwrap();
+
+        assert_eq!(grid.get_row(0), &[2, 3, 0]);
+    }
+
+    #[test]
+    fn test_scroll_right() {
+        let mut grid: Grid<i32> = Grid::new(3, 1, 0, 0);
+        grid.set(0, 0, 1).unwrap();
+        grid.set(1, 0, 2).unwrap();
+        grid.set(2, 0, 3).unwrap();
+
+        grid.scroll_right((0, 0, 3, 1), 1).unwrap();
+
+        assert_eq!(grid.get_row(0), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn test_scroll_region_leaves_outside_cells_untouched() {
+        let mut grid: Grid<i32> = Grid::new(4, 1, 0, 0);
+        grid.set(0, 0, 1).unwrap();
+        grid.set(1, 0, 2).unwrap();
+        grid.set(2, 0, 3).unwrap();
+        grid.set(3, 0, 9).unwrap();
+
+        grid.scroll_left((0, 0, 3, 1), 1).unwrap();
+
+        assert_eq!(grid.get_row(0), &[2, 3, 0, 9]);
+    }
+
+    #[test]
+    fn test_scroll_rejects_out_of_bounds_region() {
+        let mut grid: Grid<i32> = Grid::new(3, 3, 0, 0);
+
+        assert_eq!(grid.scroll_up((0, 0, 5, 5), 1), Err(()));
+        assert_eq!(grid.scroll_down((0, 0, 5, 5), 1), Err(()));
+        assert_eq!(grid.scroll_left((0, 0, 5, 5), 1), Err(()));
+        assert_eq!(grid.scroll_right((0, 0, 5, 5), 1), Err(()));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid: Grid<bool> = Grid::new(3, 3, 0, 0);
+
+        assert_eq!(grid.neighbors(1, 1, false).len(), 4);
+        assert_eq!(grid.neighbors(1, 1, true).len(), 8);
+
+        assert_eq!(grid.neighbors(0, 0, false).len(), 2);
+        assert_eq!(grid.neighbors(0, 0, true).len(), 3);
+    }
+
+    #[test]
+    fn test_flood_fill_respects_enclosing_wall() {
+        let grid = Grid::from_str_map("###\n#.#\n###").unwrap();
+
+        let region = grid.flood_fill((1, 1), |&c| c != '#');
+
+        assert_eq!(region.len(), 1);
+        assert!(region.contains(&(1, 1)));
+    }
+
     #[test]
     fn test_iterator_length() {
         let grid: Grid<bool> = Grid::new(5, 5, 0, 0);
         assert_eq!(grid.iter().map(|_| 1).count(), 25);
         assert_eq!(grid.into_iter().count(), 25);
     }
+
+    #[test]
+    fn test_iterator_double_ended() {
+        let mut grid: Grid<i32> = Grid::new(3, 2, 0, 0);
+        for (i, item) in grid.clone().into_iter().enumerate() {
+            grid.set(item.x, item.y, i as i32).unwrap();
+        }
+
+        let mut iter = grid.into_iter();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next().unwrap().element, 0);
+        assert_eq!(iter.next_back().unwrap().element, 5);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.rev().map(|item| item.element).collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+    }
 }